@@ -0,0 +1,564 @@
+use std::{fs, error::Error, sync::Arc, time::Instant};
+use serde::{Deserialize, Serialize};
+use futures::StreamExt;
+use solana_client::{
+    connection_cache::ConnectionCache,
+    nonblocking::{rpc_client::RpcClient, tpu_client::TpuClient},
+    rpc_config::RpcSignatureSubscribeConfig,
+    rpc_response::RpcSignatureResult,
+};
+use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
+use solana_quic_client::{QuicConfig, QuicConnectionManager, QuicPool};
+use solana_tpu_client::tpu_client::{TpuClientConfig, DEFAULT_TPU_CONNECTION_POOL_SIZE};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::{Signature, Signer, read_keypair_file},
+    transaction::Transaction,
+};
+use solana_system_interface::instruction as system_instruction;
+use futures::future::join_all;
+use tokio::time::{sleep, Duration};
+
+/// TPU client specialized for the QUIC transport, the only one this tool
+/// wires up via `ConnectionCache::new_quic`.
+type QuicTpuClient = TpuClient<QuicPool, QuicConnectionManager, QuicConfig>;
+
+/// How a signed transaction reaches the cluster.
+#[derive(Debug, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Submission {
+    #[default]
+    Rpc,
+    Tpu,
+}
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    rpc_url: String,
+    /// WebSocket endpoint for signature subscriptions. Defaults to `rpc_url`
+    /// with `http(s)` swapped for `ws(s)` when not set.
+    ws_url: Option<String>,
+    #[serde(default)]
+    submission: Submission,
+    /// When set, runs repeated bulk transfers and records per-run metrics
+    /// to `metrics.csv` instead of sending a single one-shot batch.
+    bench: Option<BenchConfig>,
+    /// How long to wait for confirmation before checking whether a
+    /// transfer's blockhash has expired. Defaults to 30s.
+    max_timeout_secs: Option<u64>,
+    /// How many times to re-sign and resubmit a transfer against a fresh
+    /// blockhash after it times out with an expired blockhash. Defaults to 3.
+    max_retries: Option<u32>,
+    /// When enabled, transfers without an explicit
+    /// `compute_unit_price_micro_lamports` have one derived from the median
+    /// of `get_recent_prioritization_fees` for the accounts involved.
+    #[serde(default)]
+    auto_priority_fee: bool,
+    wallets: Vec<TransferEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BenchConfig {
+    txs_per_run: usize,
+    num_of_runs: usize,
+}
+
+/// Per-run (or aggregate, when `run` is `0`) confirmation-rate metrics
+/// written to `metrics.csv`.
+#[derive(Debug, Serialize)]
+struct Metric {
+    run: usize,
+    sent: usize,
+    confirmed: usize,
+    failed: usize,
+    avg_confirmation_ms: f64,
+    median_confirmation_ms: f64,
+    p95_confirmation_ms: f64,
+}
+
+fn average(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn percentile(values: &[f64], pct: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let idx = ((pct / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[idx]
+}
+
+fn median_u64(values: &[u64]) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    sorted[sorted.len() / 2]
+}
+
+fn derive_ws_url(config: &Config) -> String {
+    config.ws_url.clone().unwrap_or_else(|| {
+        if let Some(rest) = config.rpc_url.strip_prefix("https") {
+            format!("wss{}", rest)
+        } else if let Some(rest) = config.rpc_url.strip_prefix("http") {
+            format!("ws{}", rest)
+        } else {
+            config.rpc_url.clone()
+        }
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct TransferEntry {
+    from_keypair: String,
+    to_address: String,
+    amount_sol: f64,
+    /// Optional compute unit limit, prepended as a
+    /// `ComputeBudgetInstruction::set_compute_unit_limit` when set.
+    compute_unit_limit: Option<u32>,
+    /// Optional compute unit price in micro-lamports, prepended as a
+    /// `ComputeBudgetInstruction::set_compute_unit_price` when set, or
+    /// derived automatically when `Config.auto_priority_fee` is enabled.
+    compute_unit_price_micro_lamports: Option<u64>,
+}
+
+/// Signs and submits a transfer, returning its signature and the blockhash
+/// it was signed against so the caller can tell whether a later re-sign is
+/// needed.
+async fn send_sol(
+    rpc: &RpcClient,
+    tpu: Option<&QuicTpuClient>,
+    entry: &TransferEntry,
+    auto_priority_fee: bool,
+) -> Result<(Signature, Hash), Box<dyn Error>> {
+    let from = read_keypair_file(&entry.from_keypair)?;
+    let to: Pubkey = entry.to_address.parse()?;
+
+    let lamports = (entry.amount_sol * 1_000_000_000.0) as u64;
+    let recent_blockhash = rpc.get_latest_blockhash().await?;
+
+    let mut instructions = vec![];
+    if let Some(limit) = entry.compute_unit_limit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+    let compute_unit_price = match entry.compute_unit_price_micro_lamports {
+        Some(price) => Some(price),
+        None if auto_priority_fee => {
+            let fees = rpc.get_recent_prioritization_fees(&[from.pubkey(), to]).await?;
+            let samples: Vec<u64> = fees.iter().map(|fee| fee.prioritization_fee).collect();
+            Some(median_u64(&samples))
+        }
+        None => None,
+    };
+    if let Some(price) = compute_unit_price {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    instructions.push(system_instruction::transfer(&from.pubkey(), &to, lamports));
+
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&from.pubkey()),
+        &[&from],
+        recent_blockhash,
+    );
+
+    let signature = match tpu {
+        Some(tpu) => {
+            let wire_tx = bincode::serialize(&tx)?;
+            if !tpu.send_wire_transaction(wire_tx).await {
+                return Err("TPU client failed to send transaction".into());
+            }
+            tx.signatures[0]
+        }
+        None => rpc.send_transaction(&tx).await?,
+    };
+    Ok((signature, recent_blockhash))
+}
+
+/// Sends `entry`, then waits up to `max_timeout` for confirmation. If it
+/// times out and its blockhash has since expired, re-signs the same
+/// transfer against a fresh blockhash and resubmits, up to `max_retries`
+/// times, following the latest signature as it changes across re-signs.
+async fn send_with_retry(
+    rpc: &RpcClient,
+    ws_url: &str,
+    tpu: Option<&QuicTpuClient>,
+    entry: &TransferEntry,
+    max_timeout: Duration,
+    max_retries: u32,
+    auto_priority_fee: bool,
+) -> Result<(Signature, bool), Box<dyn Error>> {
+    let (mut signature, mut blockhash) = send_sol(rpc, tpu, entry, auto_priority_fee).await?;
+    let mut retries = 0;
+
+    loop {
+        let confirmed = tokio::time::timeout(max_timeout, check_tx_status(rpc, ws_url, &signature))
+            .await
+            .unwrap_or(false);
+        if confirmed {
+            return Ok((signature, true));
+        }
+
+        // If the RPC call itself fails, assume the blockhash is still valid
+        // rather than resubmitting: the original transfer may yet land, and
+        // resubmitting on a false "expired" reading would double-spend.
+        let blockhash_valid = rpc
+            .is_blockhash_valid(&blockhash, CommitmentConfig::processed())
+            .await
+            .unwrap_or(true);
+        if blockhash_valid {
+            // The transfer hasn't landed yet, but its blockhash is still
+            // live (~150 slots), so it can still be picked up. Keep waiting
+            // instead of giving up after a single max_timeout window.
+            continue;
+        }
+
+        if retries >= max_retries {
+            return Ok((signature, false));
+        }
+
+        retries += 1;
+        println!(
+            "⟳ Blockhash expired for {}, resubmitting (retry {}/{})",
+            signature, retries, max_retries
+        );
+        let (new_signature, new_blockhash) = send_sol(rpc, tpu, entry, auto_priority_fee).await?;
+        signature = new_signature;
+        blockhash = new_blockhash;
+    }
+}
+
+/// Waits for finalized confirmation of `sig` over a WebSocket
+/// `signatureSubscribe`, falling back to polling if the socket drops.
+async fn check_tx_status(rpc: &RpcClient, ws_url: &str, sig: &Signature) -> bool {
+    match check_tx_status_ws(ws_url, sig).await {
+        Some(confirmed) => confirmed,
+        None => check_tx_status_poll(rpc, sig).await,
+    }
+}
+
+/// Subscribes to `sig` over the pubsub client and awaits the first
+/// notification. Returns `None` if the socket fails so the caller can
+/// fall back to polling.
+async fn check_tx_status_ws(ws_url: &str, sig: &Signature) -> Option<bool> {
+    let client = PubsubClient::new(ws_url).await.ok()?;
+    let (mut notifications, unsubscribe) = client
+        .signature_subscribe(
+            sig,
+            Some(RpcSignatureSubscribeConfig {
+                commitment: Some(CommitmentConfig::finalized()),
+                enable_received_notification: Some(false),
+            }),
+        )
+        .await
+        .ok()?;
+
+    let result = notifications.next().await.and_then(|notification| {
+        match notification.value {
+            RpcSignatureResult::ProcessedSignature(sig_result) => Some(sig_result.err.is_none()),
+            RpcSignatureResult::ReceivedSignature(_) => None,
+        }
+    });
+    unsubscribe().await;
+    result
+}
+
+async fn check_tx_status_poll(rpc: &RpcClient, sig: &Signature) -> bool {
+    for _ in 0..10 {
+        if let Ok(Some(result)) = rpc
+            .get_signature_status_with_commitment(sig, CommitmentConfig::finalized())
+            .await
+        {
+            return result.is_ok();
+        }
+        sleep(Duration::from_secs(2)).await;
+    }
+    false
+}
+
+/// Sends `bench.num_of_runs` batches of `bench.txs_per_run` transfers,
+/// timing each transfer individually from its own send call through to
+/// its confirmation notification, and streams one `Metric` row per run
+/// (plus a trailing aggregate row) to `metrics.csv`.
+async fn run_bench(
+    config: &Config,
+    rpc: &RpcClient,
+    ws_url: &str,
+    tpu: Option<&QuicTpuClient>,
+    bench: &BenchConfig,
+) -> Result<(), Box<dyn Error>> {
+    let max_timeout = Duration::from_secs(config.max_timeout_secs.unwrap_or(30));
+    let mut writer = csv::Writer::from_path("metrics.csv")?;
+    let mut all_latencies_ms = vec![];
+    let mut total_sent = 0;
+    let mut total_confirmed = 0;
+
+    for run in 0..bench.num_of_runs {
+        println!("\nBench run {}/{}", run + 1, bench.num_of_runs);
+
+        let entries: Vec<&TransferEntry> =
+            config.wallets.iter().cycle().take(bench.txs_per_run).collect();
+
+        let send_futures: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                let entry = *entry;
+                async move {
+                    let sent_at = Instant::now();
+                    let result = send_sol(rpc, tpu, entry, config.auto_priority_fee)
+                        .await
+                        .map(|(sig, _)| sig);
+                    (sent_at, result)
+                }
+            })
+            .collect();
+        let sent = join_all(send_futures).await;
+
+        let mut confirm_futures = Vec::new();
+        let mut failed = 0;
+        for (sent_at, result) in sent {
+            match result {
+                Ok(sig) => confirm_futures.push(async move {
+                    // Bound the wait so a notification that never arrives
+                    // (dropped tx, missed confirmation, socket hiccup)
+                    // counts as a failed/timed-out run instead of hanging
+                    // the whole bench.
+                    let confirmed = tokio::time::timeout(max_timeout, check_tx_status(rpc, ws_url, &sig))
+                        .await
+                        .unwrap_or(false);
+                    (confirmed, sent_at.elapsed())
+                }),
+                Err(e) => {
+                    println!("✘ Failed to send tx: {}", e);
+                    failed += 1;
+                }
+            }
+        }
+        let outcomes = join_all(confirm_futures).await;
+
+        let mut run_latencies_ms = vec![];
+        let mut confirmed = 0;
+        for (ok, latency) in outcomes {
+            if ok {
+                confirmed += 1;
+                run_latencies_ms.push(latency.as_secs_f64() * 1000.0);
+            } else {
+                failed += 1;
+            }
+        }
+
+        let metric = Metric {
+            run: run + 1,
+            sent: entries.len(),
+            confirmed,
+            failed,
+            avg_confirmation_ms: average(&run_latencies_ms),
+            median_confirmation_ms: percentile(&run_latencies_ms, 50.0),
+            p95_confirmation_ms: percentile(&run_latencies_ms, 95.0),
+        };
+        println!(
+            "✅ {}/{} confirmed, avg {:.1}ms, p95 {:.1}ms",
+            metric.confirmed, metric.sent, metric.avg_confirmation_ms, metric.p95_confirmation_ms
+        );
+        writer.serialize(&metric)?;
+        writer.flush()?;
+
+        total_sent += metric.sent;
+        total_confirmed += confirmed;
+        all_latencies_ms.extend(run_latencies_ms);
+    }
+
+    writer.serialize(&Metric {
+        run: 0,
+        sent: total_sent,
+        confirmed: total_confirmed,
+        failed: total_sent - total_confirmed,
+        avg_confirmation_ms: average(&all_latencies_ms),
+        median_confirmation_ms: percentile(&all_latencies_ms, 50.0),
+        p95_confirmation_ms: percentile(&all_latencies_ms, 95.0),
+    })?;
+    writer.flush()?;
+
+    println!("\nWrote metrics to metrics.csv");
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+
+    // Load config
+    let config_str = fs::read_to_string("config.yaml")?;
+    let config: Config = serde_yaml::from_str(&config_str)?;
+    let rpc = RpcClient::new(config.rpc_url.clone());
+    let ws_url = derive_ws_url(&config);
+
+    let tpu = if config.submission == Submission::Tpu {
+        let connection_cache = match ConnectionCache::new_quic(
+            "send_sol-tpu",
+            DEFAULT_TPU_CONNECTION_POOL_SIZE,
+        ) {
+            ConnectionCache::Quic(cache) => cache,
+            ConnectionCache::Udp(_) => unreachable!("new_quic always returns a Quic cache"),
+        };
+        Some(
+            QuicTpuClient::new_with_connection_cache(
+                Arc::new(RpcClient::new(config.rpc_url.clone())),
+                &ws_url,
+                TpuClientConfig::default(),
+                connection_cache,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    if let Some(bench) = &config.bench {
+        run_bench(&config, &rpc, &ws_url, tpu.as_ref(), bench).await?;
+        return Ok(());
+    }
+
+    let max_timeout = Duration::from_secs(config.max_timeout_secs.unwrap_or(30));
+    let max_retries = config.max_retries.unwrap_or(3);
+
+    // Send transactions in parallel, rebroadcasting on blockhash expiry
+    println!("Sending transfers...");
+    let futures = config.wallets.iter().map(|entry| {
+        send_with_retry(
+            &rpc,
+            &ws_url,
+            tpu.as_ref(),
+            entry,
+            max_timeout,
+            max_retries,
+            config.auto_priority_fee,
+        )
+    });
+    let results: Vec<_> = join_all(futures).await;
+
+    let mut success_count = 0;
+    let mut fail_count = 0;
+    for result in results {
+        match result {
+            Ok((sig, true)) => {
+                println!("✔ Confirmed tx: {}", sig);
+                success_count += 1;
+            }
+            Ok((sig, false)) => {
+                println!("✘ Unconfirmed tx: {}", sig);
+                fail_count += 1;
+            }
+            Err(e) => {
+                println!("✘ Failed to send tx: {}", e);
+                fail_count += 1;
+            }
+        }
+    }
+
+    println!("\n📦 Transfer complete:");
+    println!("✅ Successful: {}", success_count);
+    println!("❌ Failed: {}", fail_count);
+    println!("⏱ Duration: {:.2?}", start.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with(rpc_url: &str, ws_url: Option<&str>) -> Config {
+        Config {
+            rpc_url: rpc_url.to_string(),
+            ws_url: ws_url.map(str::to_string),
+            submission: Submission::Rpc,
+            bench: None,
+            max_timeout_secs: None,
+            max_retries: None,
+            auto_priority_fee: false,
+            wallets: vec![],
+        }
+    }
+
+    #[test]
+    fn average_of_empty_is_zero() {
+        assert_eq!(average(&[]), 0.0);
+    }
+
+    #[test]
+    fn average_of_single_value() {
+        assert_eq!(average(&[5.0]), 5.0);
+    }
+
+    #[test]
+    fn average_of_even_length() {
+        assert_eq!(average(&[1.0, 2.0, 3.0, 4.0]), 2.5);
+    }
+
+    #[test]
+    fn percentile_of_empty_is_zero() {
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn percentile_of_single_value() {
+        assert_eq!(percentile(&[42.0], 50.0), 42.0);
+    }
+
+    #[test]
+    fn percentile_of_even_length_rounds_to_nearest_index() {
+        // idx = round(0.5 * (4 - 1)) = round(1.5) = 2 -> sorted[2] == 3.0
+        assert_eq!(percentile(&[1.0, 2.0, 3.0, 4.0], 50.0), 3.0);
+    }
+
+    #[test]
+    fn percentile_p95_of_even_length() {
+        // idx = round(0.95 * (4 - 1)) = round(2.85) = 3 -> sorted[3] == 4.0
+        assert_eq!(percentile(&[1.0, 2.0, 3.0, 4.0], 95.0), 4.0);
+    }
+
+    #[test]
+    fn median_u64_of_empty_is_zero() {
+        assert_eq!(median_u64(&[]), 0);
+    }
+
+    #[test]
+    fn median_u64_of_single_value() {
+        assert_eq!(median_u64(&[7]), 7);
+    }
+
+    #[test]
+    fn median_u64_of_even_length_picks_upper_middle() {
+        // sorted.len() / 2 == 2 -> picks the upper-middle element, not an
+        // average of the two middle elements.
+        assert_eq!(median_u64(&[1, 2, 3, 4]), 3);
+    }
+
+    #[test]
+    fn derive_ws_url_uses_explicit_override() {
+        let config = config_with("https://example.com", Some("wss://override"));
+        assert_eq!(derive_ws_url(&config), "wss://override");
+    }
+
+    #[test]
+    fn derive_ws_url_derives_wss_from_https() {
+        let config = config_with("https://example.com", None);
+        assert_eq!(derive_ws_url(&config), "wss://example.com");
+    }
+
+    #[test]
+    fn derive_ws_url_derives_ws_from_http() {
+        let config = config_with("http://example.com", None);
+        assert_eq!(derive_ws_url(&config), "ws://example.com");
+    }
+}